@@ -6,7 +6,8 @@ use std::io::Read;
 use std::io::Error;
 use std::io::ErrorKind;
 use std::net::{TcpListener,TcpStream,Shutdown};
-use std::time::Duration;
+use std::time::{Duration,SystemTime,UNIX_EPOCH};
+use std::collections::BTreeSet;
 
 #[derive(Clone)]
 #[derive(Copy)]
@@ -261,6 +262,10 @@ impl FieldDisp for DayOfWeek {
 trait Convert<T> {
 	fn from_num(&self, n: usize) -> Result<T,Error>;
 	fn alpha_to_num(&self, s: &str) -> Result<usize,Error>;
+	//inclusive (min,max) of the values this field can take
+	fn domain(&self) -> (usize,usize);
+	//inverse of from_num: recover the underlying number of an already-validated value
+	fn as_num(&self, t: T) -> usize;
 }
 
 impl Convert<usize> for Minute {
@@ -274,6 +279,14 @@ impl Convert<usize> for Minute {
 	fn alpha_to_num(&self, s: &str) -> Result<usize,Error> {
 		Err(Error::new(ErrorKind::InvalidInput,format!("Invalid MINUTE value {}",s)))
 	}
+
+	fn domain(&self) -> (usize,usize) {
+		(0,59)
+	}
+
+	fn as_num(&self, t: usize) -> usize {
+		t
+	}
 }
 
 impl Convert<usize> for Hour {
@@ -287,12 +300,20 @@ impl Convert<usize> for Hour {
 	fn alpha_to_num(&self, s: &str) -> Result<usize,Error> {
 		Err(Error::new(ErrorKind::InvalidInput,format!("Invalid HOUR value {}",s)))
 	}
+
+	fn domain(&self) -> (usize,usize) {
+		(0,23)
+	}
+
+	fn as_num(&self, t: usize) -> usize {
+		t
+	}
 }
 
 impl Convert<usize> for DayOfMonth {
 	fn from_num(&self, n: usize) -> Result<usize,Error> {
 		match n {
-			1..=23 => Ok(n),
+			1..=31 => Ok(n),
 			_ => Err(Error::new(ErrorKind::InvalidInput,format!("Invalid DAY-OF-MONTH value {}",n))),
 		}
 	}
@@ -300,6 +321,14 @@ impl Convert<usize> for DayOfMonth {
 	fn alpha_to_num(&self, s: &str) -> Result<usize,Error> {
 		Err(Error::new(ErrorKind::InvalidInput,format!("Invalid DAY-OF-MONTH value {}",s)))
 	}
+
+	fn domain(&self) -> (usize,usize) {
+		(1,31)
+	}
+
+	fn as_num(&self, t: usize) -> usize {
+		t
+	}
 }
 
 impl Convert<&str> for Month {
@@ -329,6 +358,14 @@ impl Convert<&str> for Month {
 			None => Err(Error::new(ErrorKind::InvalidInput,format!("Invalid DAY-OF-WEEK value {}",s))),
 		}
 	}
+
+	fn domain(&self) -> (usize,usize) {
+		(1,12)
+	}
+
+	fn as_num(&self, t: &str) -> usize {
+		self.alpha_to_num(t).unwrap()
+	}
 }
 
 impl Convert<&str> for DayOfWeek {
@@ -353,6 +390,14 @@ impl Convert<&str> for DayOfWeek {
 			None => Err(Error::new(ErrorKind::InvalidInput,format!("Invalid DAY-OF-WEEK value {}",s))),
 		}
 	}
+
+	fn domain(&self) -> (usize,usize) {
+		(0,6)
+	}
+
+	fn as_num(&self, t: &str) -> usize {
+		self.alpha_to_num(t).unwrap()
+	}
 }
 
 struct Field<F,P> {
@@ -445,6 +490,37 @@ impl<'a, F: Convert<P>, P> Field<F,P>
 			_ => return Err(Error::new(ErrorKind::InvalidInput,"Invalid char after number")),
 		}
 	}
+
+	//expand the field's patterns into the sorted set of domain values they match
+	fn expand(&self) -> BTreeSet<usize> where P: Copy {
+		let (dmin,dmax) = self.typ.domain();
+		let mut set = BTreeSet::new();
+		for pat in self.pats.iter() {
+			match pat {
+				Pattern::At(v) => {
+					set.insert(self.typ.as_num(*v));
+				},
+				Pattern::Every(step) => {
+					let mut d = dmin;
+					while d <= dmax {
+						if (d - dmin) % step == 0 {
+							set.insert(d);
+						}
+						d += 1;
+					}
+				},
+				Pattern::Range(a,b,step) => {
+					let mut v = self.typ.as_num(*a);
+					let end = self.typ.as_num(*b);
+					while v <= end {
+						set.insert(v);
+						v += step;
+					}
+				},
+			}
+		}
+		set
+	}
 }
 
 trait Printer<T> {
@@ -483,6 +559,204 @@ impl<P: fmt::Display, F: FieldDisp, T: io::Write> Printer<T> for Field<F,P> {
 	}
 }
 
+//civil calendar timestamp truncated to minute resolution (no seconds, no timezone - always UTC)
+#[derive(Clone,Copy,Debug)]
+struct DateTime {
+	year: i64,
+	month: usize,
+	day: usize,
+	hour: usize,
+	minute: usize,
+}
+
+impl fmt::Display for DateTime {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f,"{:04}-{:02}-{:02} {:02}:{:02}",self.year,self.month,self.day,self.hour,self.minute)
+	}
+}
+
+impl DateTime {
+	fn is_leap_year(year: i64) -> bool {
+		(year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+	}
+
+	fn days_in_month(year: i64, month: usize) -> usize {
+		match month {
+			1|3|5|7|8|10|12 => 31,
+			4|6|9|11 => 30,
+			2 => if DateTime::is_leap_year(year) { 29 } else { 28 },
+			_ => panic!(),
+		}
+	}
+
+	//Howard Hinnant's days-from-civil algorithm, shifted so day 0 is 1970-01-01
+	fn days_from_civil(year: i64, month: usize, day: usize) -> i64 {
+		let y = if month <= 2 { year - 1 } else { year };
+		let era = if y >= 0 { y } else { y - 399 } / 400;
+		let yoe = (y - era * 400) as i64;
+		let mp = (month as i64 + 9) % 12;
+		let doy = (153 * mp + 2) / 5 + day as i64 - 1;
+		let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+		era * 146097 + doe - 719468
+	}
+
+	//inverse of days_from_civil
+	fn civil_from_days(days: i64) -> (i64,usize,usize) {
+		let z = days + 719468;
+		let era = if z >= 0 { z } else { z - 146096 } / 146097;
+		let doe = z - era * 146097;
+		let yoe = (doe - doe/1460 + doe/36524 - doe/146096) / 365;
+		let y = yoe + era * 400;
+		let doy = doe - (365*yoe + yoe/4 - yoe/100);
+		let mp = (5*doy + 2)/153;
+		let day = (doy - (153*mp+2)/5 + 1) as usize;
+		let month = if mp < 10 { mp + 3 } else { mp - 9 } as usize;
+		let year = if month <= 2 { y + 1 } else { y };
+		(year,month,day)
+	}
+
+	//current time, truncated down to the whole minute it falls in
+	fn now() -> DateTime {
+		let secs = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() as i64;
+		DateTime::from_unix_minute(secs / 60)
+	}
+
+	fn from_unix_minute(total_minutes: i64) -> DateTime {
+		let days = total_minutes.div_euclid(1440);
+		let minute_of_day = total_minutes.rem_euclid(1440);
+		let (year,month,day) = DateTime::civil_from_days(days);
+		DateTime { year, month, day, hour: (minute_of_day/60) as usize, minute: (minute_of_day%60) as usize }
+	}
+
+	//0 = Sunday .. 6 = Saturday, as used by the DayOfWeek field
+	fn weekday(&self) -> usize {
+		let days = DateTime::days_from_civil(self.year,self.month,self.day);
+		//1970-01-01 was a Thursday
+		((days % 7 + 4 + 7) % 7) as usize
+	}
+
+	fn advance_month(&mut self) {
+		self.month += 1;
+		if self.month > 12 {
+			self.month = 1;
+			self.year += 1;
+		}
+	}
+
+	fn advance_day(&mut self) {
+		self.day += 1;
+		if self.day > DateTime::days_in_month(self.year,self.month) {
+			self.day = 1;
+			self.advance_month();
+		}
+	}
+
+	fn advance_hour(&mut self) {
+		self.hour += 1;
+		if self.hour >= 24 {
+			self.hour = 0;
+			self.advance_day();
+		}
+	}
+
+	fn advance_minute(&mut self) {
+		self.minute += 1;
+		if self.minute >= 60 {
+			self.minute = 0;
+			self.advance_hour();
+		}
+	}
+
+	fn jump_to_next_month(&mut self) {
+		self.day = 1;
+		self.hour = 0;
+		self.minute = 0;
+		self.advance_month();
+	}
+
+	fn jump_to_next_day(&mut self) {
+		self.hour = 0;
+		self.minute = 0;
+		self.advance_day();
+	}
+
+	fn jump_to_next_hour(&mut self) {
+		self.minute = 0;
+		self.advance_hour();
+	}
+}
+
+//safety cap on how far into the future the search for occurrences is allowed to run
+const NEXT_SEARCH_MINUTE_CAP: i64 = 4 * 366 * 24 * 60;
+
+impl Field<Minute,usize> {
+	//compute the next N firing times at or after `from`, per the parsed min/hrs/dom/mon/dow fields
+	fn next_occurrences(
+		from: DateTime,
+		min: &Field<Minute,usize>,
+		hrs: &Field<Hour,usize>,
+		dom: &Field<DayOfMonth,usize>,
+		mon: &Field<Month,&str>,
+		dow: &Field<DayOfWeek,&str>,
+		count: usize,
+	) -> Vec<DateTime> {
+		let min_set = min.expand();
+		let hrs_set = hrs.expand();
+		let dom_set = dom.expand();
+		let mon_set = mon.expand();
+		let dow_set = dow.expand();
+
+		//POSIX: when both dom and dow are restricted, a day matches if it satisfies either one
+		let dom_restricted = dom.pats.iter().find(|p| !matches!(p,Pattern::Every(1))).is_some();
+		let dow_restricted = dow.pats.iter().find(|p| !matches!(p,Pattern::Every(1))).is_some();
+
+		let day_matches = |t: &DateTime| -> bool {
+			let dom_ok = dom_set.contains(&t.day);
+			let dow_ok = dow_set.contains(&t.weekday());
+			match (dom_restricted,dow_restricted) {
+				(true,true) => dom_ok || dow_ok,
+				(true,false) => dom_ok,
+				(false,true) => dow_ok,
+				(false,false) => true,
+			}
+		};
+
+		let mut t = from;
+		t.advance_minute();
+		let start = DateTime::days_from_civil(t.year,t.month,t.day) * 1440 + (t.hour*60 + t.minute) as i64;
+
+		let mut out = Vec::new();
+		loop {
+			let now_minute = DateTime::days_from_civil(t.year,t.month,t.day) * 1440 + (t.hour*60 + t.minute) as i64;
+			if now_minute - start > NEXT_SEARCH_MINUTE_CAP {
+				break;
+			}
+			if !mon_set.contains(&t.month) {
+				t.jump_to_next_month();
+				continue;
+			}
+			if !day_matches(&t) {
+				t.jump_to_next_day();
+				continue;
+			}
+			if !hrs_set.contains(&t.hour) {
+				t.jump_to_next_hour();
+				continue;
+			}
+			if !min_set.contains(&t.minute) {
+				t.advance_minute();
+				continue;
+			}
+			out.push(t);
+			if out.len() >= count {
+				break;
+			}
+			t.advance_minute();
+		}
+		out
+	}
+}
+
 enum FieldType {
 	Minute,
 	Hour,
@@ -492,22 +766,151 @@ enum FieldType {
 	End,
 }
 
-fn respond(req: String, out: &mut TcpStream) -> Result<(),Error> {
-	//handle @xxxx inputs by converting them to equivalent CRON expressions
-	let mapstr = match req.lines().nth(0).unwrap() {
+//the five fields of a CRON expression, built up piecewise while compiling an NL phrase
+struct CronFields {
+	minute: String,
+	hour: String,
+	dom: String,
+	month: String,
+	dow: String,
+}
+
+impl CronFields {
+	fn wildcard() -> Self {
+		Self { minute: String::from("*"), hour: String::from("*"), dom: String::from("*"), month: String::from("*"), dow: String::from("*") }
+	}
+
+	fn to_cron(&self) -> String {
+		format!("{} {} {} {} {}",self.minute,self.hour,self.dom,self.month,self.dow)
+	}
+}
+
+//first three letters, uppercased - matches the alpha_to_num tables' key format
+fn weekday_num(word: &str) -> Result<usize,Error> {
+	let key: String = word.chars().take(3).collect::<String>().to_ascii_uppercase();
+	DayOfWeek{}.alpha_to_num(&key)
+}
+
+//"9" or "9:30" -> validated (hour,minute), reusing the existing field range checks
+fn parse_time(s: &str) -> Result<(usize,usize),Error> {
+	let mut parts = s.splitn(2,':');
+	let h: usize = parts.next().unwrap().parse()
+		.map_err(|_| Error::new(ErrorKind::InvalidInput,format!("Invalid hour {}",s)))?;
+	Hour{}.from_num(h)?;
+	let m: usize = match parts.next() {
+		Some(ms) => ms.parse().map_err(|_| Error::new(ErrorKind::InvalidInput,format!("Invalid minute {}",s)))?,
+		None => 0,
+	};
+	Minute{}.from_num(m)?;
+	Ok((h,m))
+}
+
+//a small recursive descent over whitespace-separated words, e.g. "every monday at 08:00"
+fn nl_to_cron(phrase: &str) -> Result<String,Error> {
+	let words: Vec<String> = phrase.split_whitespace().map(|w| w.to_ascii_lowercase()).collect();
+	if words.is_empty() {
+		return Err(Error::new(ErrorKind::InvalidInput,"Empty schedule phrase"));
+	}
+
+	let mut fields = CronFields::wildcard();
+	let mut idx = 1;
+	match words[0].as_str() {
+		"secondly"|"minutely" => (),
+		"hourly" => fields.minute = String::from("0"),
+		"daily" => {
+			fields.minute = String::from("0");
+			fields.hour = String::from("0");
+		},
+		"weekly" => {
+			fields.minute = String::from("0");
+			fields.hour = String::from("0");
+			fields.dow = String::from("0");
+		},
+		"monthly" => {
+			fields.minute = String::from("0");
+			fields.hour = String::from("0");
+			fields.dom = String::from("1");
+		},
+		"yearly" => {
+			fields.minute = String::from("0");
+			fields.hour = String::from("0");
+			fields.dom = String::from("1");
+			fields.month = String::from("1");
+		},
+		"every" => {
+			if words.len() < 2 {
+				return Err(Error::new(ErrorKind::InvalidInput,"Expected something after 'every'"));
+			}
+			if let Ok(d) = weekday_num(&words[1]) {
+				fields.minute = String::from("0");
+				fields.hour = String::from("0");
+				fields.dow = d.to_string();
+				idx = 2;
+			} else {
+				let n: usize = words[1].parse()
+					.map_err(|_| Error::new(ErrorKind::InvalidInput,format!("Unknown token {}",words[1])))?;
+				if words.len() < 3 {
+					return Err(Error::new(ErrorKind::InvalidInput,"Expected a unit after 'every <n>'"));
+				}
+				match words[2].as_str() {
+					"minute"|"minutes" => fields.minute = format!("*/{}",n),
+					"hour"|"hours" => {
+						fields.minute = String::from("0");
+						fields.hour = format!("*/{}",n);
+					},
+					"day"|"days" => {
+						fields.minute = String::from("0");
+						fields.hour = String::from("0");
+						fields.dom = format!("*/{}",n);
+					},
+					_ => return Err(Error::new(ErrorKind::InvalidInput,format!("Unknown unit {}",words[2]))),
+				}
+				idx = 3;
+			}
+		},
+		w => {
+			let d = weekday_num(w)?;
+			fields.minute = String::from("0");
+			fields.hour = String::from("0");
+			fields.dow = d.to_string();
+		},
+	}
+
+	if idx < words.len() {
+		if words[idx] != "at" {
+			return Err(Error::new(ErrorKind::InvalidInput,format!("Unknown token {}",words[idx])));
+		}
+		idx += 1;
+		if idx >= words.len() {
+			return Err(Error::new(ErrorKind::InvalidInput,"Expected a time after 'at'"));
+		}
+		let (h,m) = parse_time(&words[idx])?;
+		fields.hour = h.to_string();
+		fields.minute = m.to_string();
+		idx += 1;
+	}
+
+	if idx != words.len() {
+		return Err(Error::new(ErrorKind::InvalidInput,format!("Unknown token {}",words[idx])));
+	}
+
+	Ok(fields.to_cron())
+}
+
+//translate @xxxx shorthands into their equivalent CRON expression
+fn expand_alias(line: &str) -> String {
+	match line {
 		"@yearly"|"@annually" => String::from("0 0 1 1 *"),
 		"@monthly" => String::from("0 0 1 * *"),
 		"@weekly" => String::from("0 0 * * 0"),
 		"@daily" => String::from("0 0 * * *"),
 		"@hourly" => String::from("0 * * * *"),
-		"@reboot" => {
-			//no need to translate this
-			write!(out,"Run after reboot")?;
-			return Ok(());
-		}
-		_ => req,
-	};
+		_ => String::from(line),
+	}
+}
 
+//tokenize and parse a five-field CRON expression into its per-field representation
+fn parse_fields(mapstr: &str) -> Result<(Field<Minute,usize>,Field<Hour,usize>,Field<DayOfMonth,usize>,Field<Month,&str>,Field<DayOfWeek,&str>),Error> {
 	let tokiter = TokenIter::new(mapstr.chars().collect());
 	if !matches!(tokiter.last().unwrap(),Token::EOF) {
 		return Err(Error::new(ErrorKind::InvalidInput,"CRON syntax error"));
@@ -539,10 +942,7 @@ fn respond(req: String, out: &mut TcpStream) -> Result<(),Error> {
 			Token::EOF => {
 				break;
 			},
-			_ => {
-				write!(out,"Pattern doesn't start/end on correct token\n")?;
-				return Err(Error::new(ErrorKind::InvalidInput,"Bad CRON"));
-			}
+			_ => return Err(Error::new(ErrorKind::InvalidInput,"Pattern doesn't start/end on correct token")),
 		};
 		ptr = match state {
 			FieldType::Minute => min.from_toks(ptr)?,
@@ -568,6 +968,140 @@ fn respond(req: String, out: &mut TcpStream) -> Result<(),Error> {
 		return Err(Error::new(ErrorKind::InvalidInput,"Incomplete CRON"));
 	}
 
+	Ok((min,hrs,dom,mon,dow))
+}
+
+//print the next `count` firing times of `expr` (as given after the `next <count>` verb)
+fn respond_next(count: usize, expr: &str, out: &mut TcpStream) -> Result<(),Error> {
+	let mapstr = expand_alias(expr);
+	let (min,hrs,dom,mon,dow) = parse_fields(&mapstr)?;
+
+	let occurrences = Field::next_occurrences(DateTime::now(),&min,&hrs,&dom,&mon,&dow,count);
+	if occurrences.is_empty() {
+		write!(out,"No occurrence found within the search window\n")?;
+	}
+	for t in occurrences.iter() {
+		write!(out,"{}\n",t)?;
+	}
+	Ok(())
+}
+
+fn format_num_set(set: &BTreeSet<usize>) -> String {
+	set.iter().map(|v| v.to_string()).collect::<Vec<String>>().join(",")
+}
+
+fn format_dow_set(set: &BTreeSet<usize>) -> String {
+	const NAMES: [&str;7] = ["SU","MO","TU","WE","TH","FR","SA"];
+	set.iter().map(|v| NAMES[*v]).collect::<Vec<&str>>().join(",")
+}
+
+//a field is "bare" if it is an unconstrained "*"
+fn is_bare<T>(pats: &[Pattern<T>]) -> bool {
+	pats.len() == 1 && matches!(pats[0],Pattern::Every(1))
+}
+
+//the step of a field expressed as "*/n" or "a-b/n", if it has exactly one such pattern
+fn pattern_step(pats: &[Pattern<usize>]) -> Option<usize> {
+	if pats.len() != 1 {
+		return None;
+	}
+	match pats[0] {
+		Pattern::Every(n) if n > 1 => Some(n),
+		Pattern::Range(_,_,n) if n > 1 => Some(n),
+		_ => None,
+	}
+}
+
+//translate the parsed fields into an RFC 5545 RRULE string
+fn to_rrule(min: &Field<Minute,usize>, hrs: &Field<Hour,usize>, dom: &Field<DayOfMonth,usize>, mon: &Field<Month,&str>, dow: &Field<DayOfWeek,&str>) -> String {
+	let minute_fixed = min.pats.len() == 1 && matches!(min.pats[0],Pattern::At(_));
+	let hour_fixed = hrs.pats.len() == 1 && matches!(hrs.pats[0],Pattern::At(_));
+	let is_dom_defined = !is_bare(&dom.pats);
+	let is_mon_defined = !is_bare(&mon.pats);
+	let is_dow_defined = !is_bare(&dow.pats);
+	let is_hour_defined = !is_bare(&hrs.pats);
+	let is_min_defined = !is_bare(&min.pats);
+
+	//FREQ is the coarsest period that still pins every constraint
+	let (freq,interval) = if !minute_fixed {
+		("MINUTELY",pattern_step(&min.pats))
+	} else if !hour_fixed {
+		("HOURLY",pattern_step(&hrs.pats))
+	} else if is_dow_defined {
+		("WEEKLY",None)
+	} else if is_mon_defined {
+		("YEARLY",None)
+	} else if is_dom_defined {
+		("MONTHLY",None)
+	} else {
+		("DAILY",None)
+	};
+
+	let mut rule = format!("FREQ={}",freq);
+	if let Some(n) = interval {
+		rule += &format!(";INTERVAL={}",n);
+	}
+	if is_mon_defined {
+		rule += &format!(";BYMONTH={}",format_num_set(&mon.expand()));
+	}
+	if is_dom_defined {
+		rule += &format!(";BYMONTHDAY={}",format_num_set(&dom.expand()));
+	}
+	if is_dow_defined {
+		rule += &format!(";BYDAY={}",format_dow_set(&dow.expand()));
+	}
+	if is_hour_defined {
+		rule += &format!(";BYHOUR={}",format_num_set(&hrs.expand()));
+	}
+	if is_min_defined {
+		rule += &format!(";BYMINUTE={}",format_num_set(&min.expand()));
+	}
+	rule
+}
+
+//print the RRULE equivalent of `expr` (as given after the `rrule` verb)
+fn respond_rrule(expr: &str, out: &mut TcpStream) -> Result<(),Error> {
+	let mapstr = expand_alias(expr);
+	let (min,hrs,dom,mon,dow) = parse_fields(&mapstr)?;
+	write!(out,"{}\n",to_rrule(&min,&hrs,&dom,&mon,&dow))?;
+	Ok(())
+}
+
+fn respond(req: String, out: &mut TcpStream) -> Result<(),Error> {
+	let line = match req.lines().nth(0) {
+		Some(l) => l,
+		None => return Err(Error::new(ErrorKind::InvalidInput,"Empty request")),
+	};
+
+	if let Some(rest) = line.strip_prefix("next ") {
+		let mut parts = rest.splitn(2,' ');
+		let count: usize = match parts.next().unwrap().parse() {
+			Ok(n) => n,
+			Err(_) => return Err(Error::new(ErrorKind::InvalidInput,"Invalid occurrence count")),
+		};
+		let expr = parts.next().unwrap_or("");
+		return respond_next(count,expr,out);
+	}
+
+	if let Some(rest) = line.strip_prefix("rrule ") {
+		return respond_rrule(rest,out);
+	}
+
+	if line == "@reboot" {
+		//no need to translate this
+		write!(out,"Run after reboot")?;
+		return Ok(());
+	}
+
+	const NL_ADVERBS: [&str;7] = ["secondly","minutely","hourly","daily","weekly","monthly","yearly"];
+	let first_word = line.split_whitespace().next().unwrap_or("").to_ascii_lowercase();
+	let mapstr = if first_word == "every" || NL_ADVERBS.contains(&first_word.as_str()) || weekday_num(&first_word).is_ok() {
+		nl_to_cron(line)?
+	} else {
+		expand_alias(line)
+	};
+	let (min,hrs,dom,mon,dow) = parse_fields(&mapstr)?;
+
 	out.write(b"Run\n")?;
 
 	if min.pats.len() == 1 && hrs.pats.len() == 1 && matches!(min.pats[0],Pattern::At(_)) && matches!(hrs.pats[0],Pattern::At(_)) {
@@ -618,6 +1152,35 @@ fn respond(req: String, out: &mut TcpStream) -> Result<(),Error> {
 	Ok(())
 }
 
+//a sane cap on a single request's length, well past any real CRON expression or NL phrase
+const MAX_REQUEST_LEN: usize = 4096;
+
+//read one line (without its trailing '\n') off `stream`, stopping at EOF; None once nothing is left to read
+fn read_request_line(stream: &mut TcpStream) -> Result<Option<String>,Error> {
+	let mut buf = Vec::new();
+	let mut byte = [0u8; 1];
+	loop {
+		match stream.read(&mut byte)? {
+			0 => {
+				if buf.is_empty() {
+					return Ok(None);
+				}
+				break;
+			},
+			_ => {
+				if byte[0] == b'\n' {
+					break;
+				}
+				buf.push(byte[0]);
+				if buf.len() > MAX_REQUEST_LEN {
+					return Err(Error::new(ErrorKind::InvalidInput,"Request too long"));
+				}
+			},
+		}
+	}
+	Ok(Some(String::from_utf8_lossy(&buf).into_owned()))
+}
+
 fn main() {
 	let srv = TcpListener::bind("0.0.0.0:6000").unwrap();
 	println!("Listening");
@@ -626,24 +1189,29 @@ fn main() {
 		match stream {
 			Ok(mut stream) => {
 				thread::spawn(move || {
-					let mut buf = [0u8; 64];
 					let _ = stream.set_read_timeout(Some(Duration::from_secs(30)));
-					match stream.read(&mut buf) {
-						Ok(_) => {
-							let req = String::from_utf8_lossy(&buf);
-							println!("{}",req);
-							match respond(req.to_string(),&mut stream) {
-								Ok(_) => {
-									let _ = write!(stream,"\n\n\u{1f426} \u{001b}[36;1m@bufrsh\u{001b}[0m ");
-								},
-								Err(e) => {
-									let _ = write!(stream,"{}",e.to_string());
-								},
-							}
-							let _ = stream.shutdown(Shutdown::Both);
-						},
-						Err(e) => println!("read ERR: {}",e),
+					loop {
+						match read_request_line(&mut stream) {
+							Ok(Some(req)) => {
+								println!("{}",req);
+								match respond(req,&mut stream) {
+									Ok(_) => {
+										let _ = write!(stream,"\n\n\u{1f426} \u{001b}[36;1m@bufrsh\u{001b}[0m ");
+									},
+									Err(e) => {
+										let _ = write!(stream,"{}",e.to_string());
+									},
+								}
+							},
+							Ok(None) => break,
+							Err(e) => {
+								println!("read ERR: {}",e);
+								let _ = write!(stream,"{}",e.to_string());
+								break;
+							},
+						}
 					}
+					let _ = stream.shutdown(Shutdown::Both);
 				});
 			},
 			Err(e) => {